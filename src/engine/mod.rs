@@ -8,6 +8,7 @@ use crate::{
     },
     sound::context::Context,
     engine::{resource_manager::ResourceManager, error::EngineError},
+    utils::log::Log,
     gui::{
         UserInterface,
     },
@@ -94,7 +95,9 @@ impl Engine {
         let client_size = self.context.window().inner_size();
         let aspect_ratio = client_size.width as f32 / client_size.height as f32;
 
-        self.resource_manager.update();
+        if let Err(e) = self.resource_manager.update() {
+            Log::writeln(format!("Failed to update resource manager: {}", e));
+        }
 
         for scene in self.scenes.iter_mut() {
             scene.update(aspect_ratio, dt);
@@ -120,7 +123,9 @@ impl Visit for Engine {
         visitor.enter_region(name)?;
 
         if visitor.is_reading() {
-            self.resource_manager.update();
+            if let Err(e) = self.resource_manager.update() {
+                Log::writeln(format!("Failed to update resource manager: {}", e));
+            }
             self.scenes.clear();
         }
 
@@ -129,7 +134,9 @@ impl Visit for Engine {
         self.sound_context.lock()?.visit("SoundContext", visitor)?;
 
         if visitor.is_reading() {
-            self.resource_manager.reload_resources();
+            if let Err(e) = self.resource_manager.reload_resources() {
+                Log::writeln(format!("Failed to reload resources: {}", e));
+            }
             for scene in self.scenes.iter_mut() {
                 scene.resolve();
             }
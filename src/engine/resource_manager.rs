@@ -0,0 +1,221 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{mpsc::{channel, Receiver}, Arc, Mutex},
+    time::Duration,
+};
+use notify::{watcher, DebouncedEvent, RecommendedWatcher, RecursiveMode, Watcher};
+use crate::{
+    resource::texture::{Texture, TextureKind},
+    core::visitor::{Visit, VisitResult, Visitor},
+    utils::log::Log,
+};
+
+/// How long the watcher waits for a burst of events on the same file to go
+/// quiet before firing a single reload, since editors write files in chunks.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Background filesystem watcher backing [`ResourceManager::set_watch_resources`].
+/// Torn down by simply dropping it when watching is disabled.
+struct ResourceWatcher {
+    watcher: RecommendedWatcher,
+    events: Receiver<DebouncedEvent>,
+}
+
+impl ResourceWatcher {
+    fn new() -> Option<Self> {
+        let (tx, events) = channel();
+        match watcher(tx, WATCH_DEBOUNCE) {
+            Ok(watcher) => Some(Self { watcher, events }),
+            Err(e) => {
+                Log::writeln(format!("Unable to start resource watcher: {:?}", e));
+                None
+            }
+        }
+    }
+
+    fn watch(&mut self, path: &Path) {
+        if let Err(e) = self.watcher.watch(path, RecursiveMode::NonRecursive) {
+            Log::writeln(format!("Unable to watch resource {:?}: {:?}", path, e));
+        }
+    }
+}
+
+/// Canonicalizes `path` for use as a `textures` key, falling back to `path`
+/// if it doesn't exist yet. `notify` reports watched files by canonical
+/// path, so a texture must be keyed the same way or its hot-reload events
+/// never match.
+fn canonical_key(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Owns every loaded resource (currently textures) and hands out shared
+/// handles to them, deduping repeated requests for the same resource so
+/// callers never decode the same data twice.
+pub struct ResourceManager {
+    /// Keyed by `Texture::path` - a real file path for disk-backed textures,
+    /// or a synthetic `<embedded>/...` key for textures decoded from an
+    /// in-memory buffer (see [`ResourceManager::request_texture_from_memory`]).
+    textures: HashMap<PathBuf, Arc<Mutex<Texture>>>,
+    /// `Some` only while hot-reloading is enabled - see
+    /// [`ResourceManager::set_watch_resources`].
+    watcher: Option<ResourceWatcher>,
+}
+
+impl ResourceManager {
+    pub fn new() -> Self {
+        Self {
+            textures: HashMap::new(),
+            watcher: None,
+        }
+    }
+
+    /// Opts into (or back out of) hot-reloading: when enabled, a debounced
+    /// background thread watches every disk-backed texture's source file and
+    /// queues it for reload when it changes. Disabled by default.
+    pub fn set_watch_resources(&mut self, enable: bool) {
+        if !enable {
+            self.watcher = None;
+            return;
+        }
+
+        if let Some(mut watcher) = ResourceWatcher::new() {
+            for path in self.textures.keys() {
+                if path.exists() {
+                    watcher.watch(path);
+                }
+            }
+            self.watcher = Some(watcher);
+        }
+    }
+
+    /// Requests a texture loaded from a file on disk, returning the
+    /// existing handle if it was already requested rather than decoding it
+    /// again.
+    pub fn request_texture<P: AsRef<Path>>(&mut self, path: P, kind: TextureKind) -> Result<Arc<Mutex<Texture>>, image::ImageError> {
+        let path = path.as_ref().to_path_buf();
+        let key = canonical_key(&path);
+
+        if let Some(existing) = self.textures.get(&key) {
+            return Ok(existing.clone());
+        }
+
+        let mut texture = Texture::load_from_file(&path, kind, false)?;
+        texture.path = key.clone();
+        let texture = Arc::new(Mutex::new(texture));
+
+        if let Some(watcher) = &mut self.watcher {
+            watcher.watch(&key);
+        }
+
+        self.textures.insert(key, texture.clone());
+        Ok(texture)
+    }
+
+    /// Requests a texture decoded from an in-memory byte buffer - e.g. a
+    /// glTF `.bin` buffer view or a base64-embedded image - and registers it
+    /// under the synthetic `key` rather than a real file path. Several
+    /// meshes in one imported model commonly reference the same embedded
+    /// image by the same `key`; subsequent requests for a `key` already seen
+    /// hand back the same `Arc<Mutex<Texture>>` instead of decoding again.
+    pub fn request_texture_from_memory(&mut self, key: &str, bytes: &[u8], kind: TextureKind) -> Result<Arc<Mutex<Texture>>, image::ImageError> {
+        let synthetic_path = PathBuf::from(format!("<embedded>/{}", key));
+
+        if let Some(existing) = self.textures.get(&synthetic_path) {
+            return Ok(existing.clone());
+        }
+
+        let mut texture = Texture::load_from_memory(bytes, kind, false)?;
+        texture.path = synthetic_path.clone();
+
+        let texture = Arc::new(Mutex::new(texture));
+        self.textures.insert(synthetic_path, texture.clone());
+        Ok(texture)
+    }
+
+    /// Called once per engine tick to drive any background work (such as
+    /// the hot-reload watcher) that resources may have queued up.
+    pub fn update(&mut self) -> Result<(), String> {
+        let Some(watcher) = &self.watcher else {
+            return Ok(());
+        };
+
+        let changed_paths: Vec<PathBuf> = watcher.events
+            .try_iter()
+            .filter_map(|event| match event {
+                DebouncedEvent::Write(path) | DebouncedEvent::Create(path) => Some(path),
+                _ => None,
+            })
+            .collect();
+
+        for path in changed_paths {
+            if let Some(texture) = self.textures.get(&path) {
+                match texture.lock().map_err(|e| e.to_string())?.reload() {
+                    Ok(_) => Log::writeln(format!("Texture {:?} hot-reloaded.", path)),
+                    Err(e) => Log::writeln(format!("Failed to hot-reload texture {:?}: {:?}", path, e)),
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Drops every loaded resource. Reading a `ResourceManager` through
+    /// `Visit` already clears its textures without going through here.
+    pub fn clear(&mut self) {
+        self.textures.clear();
+    }
+
+    /// Re-runs `load_from_file` for every disk-backed texture that isn't
+    /// already loaded, restoring `bytes` after deserializing a scene that
+    /// only persisted `path`/`kind`.
+    pub fn reload_resources(&mut self) -> Result<(), String> {
+        for texture in self.textures.values() {
+            let mut texture = texture.lock().map_err(|e| e.to_string())?;
+            if !texture.is_render_target() && !texture.is_loaded() {
+                let _ = texture.reload();
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for ResourceManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Visit for ResourceManager {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        visitor.enter_region(name)?;
+
+        let mut count = self.textures.len() as u32;
+        count.visit("TextureCount", visitor)?;
+
+        if visitor.is_reading() {
+            self.textures.clear();
+            for i in 0..count {
+                let mut texture = Texture::default();
+                texture.visit(&format!("Texture{}", i), visitor)?;
+
+                // Render targets all deserialize with an empty `path`; key
+                // them by index instead so two in the same scene don't
+                // collide on that shared empty key.
+                let key = if texture.is_render_target() {
+                    PathBuf::from(format!("<render-target>/{}", i))
+                } else {
+                    texture.path.clone()
+                };
+                self.textures.insert(key, Arc::new(Mutex::new(texture)));
+            }
+        } else {
+            for (i, texture) in self.textures.values().enumerate() {
+                texture.lock()?.visit(&format!("Texture{}", i), visitor)?;
+            }
+        }
+
+        visitor.leave_region()
+    }
+}
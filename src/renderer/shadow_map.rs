@@ -0,0 +1,566 @@
+use std::collections::HashMap;
+use crate::{
+    core::{
+        math::{vec3::Vec3, mat4::Mat4},
+        pool::Handle,
+    },
+    renderer::gpu_texture::{GpuTexture, GpuTextureKind, PixelKind},
+    scene::node::Node,
+};
+
+/// Controls how a shadow map is sampled when computing the occlusion term for
+/// a fragment in the main lighting pass.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ShadowMapFilter {
+    /// Single tap, hard-edged shadows. Cheapest option.
+    None,
+    /// Single comparison tap. `sampleShadow` in [`SHADOW_SAMPLING_GLSL`] reads
+    /// the map through a plain `sampler2D`, so this samples exactly like `None`;
+    /// no hardware 2x2 comparison filtering is applied.
+    Hardware2x2,
+    /// Averages several taps on a Poisson disk rotated per-pixel to hide banding.
+    Pcf,
+    /// Blocker search + penumbra estimation on top of `Pcf`, producing
+    /// contact-hardening soft shadows at the cost of an extra texture pass.
+    Pcss,
+}
+
+impl Default for ShadowMapFilter {
+    fn default() -> Self {
+        ShadowMapFilter::Pcf
+    }
+}
+
+/// Per-light shadow rendering settings. A `ShadowSettings` is attached to
+/// every shadow-casting light and controls the resolution and look of its
+/// shadow map independently of every other light in the scene.
+#[derive(Copy, Clone, Debug)]
+pub struct ShadowSettings {
+    /// Whether this light casts shadows at all.
+    pub enabled: bool,
+    /// Width/height of the depth map in texels. Point lights allocate six
+    /// faces of this size, directional and spot lights allocate one.
+    pub size: usize,
+    /// Constant part of the slope-scaled depth bias, used to avoid shadow acne.
+    pub bias: f32,
+    /// Additional bias applied proportionally to the surface slope relative
+    /// to the light, so near-grazing surfaces don't self-shadow.
+    pub slope_bias: f32,
+    /// Number of Poisson disk taps used by the `Pcf` and `Pcss` filters.
+    pub pcf_samples: usize,
+    /// Radius of the Poisson disk (in shadow map texels) that `Pcf` samples spread over.
+    pub pcf_radius: f32,
+    /// World-space size of the light used by `Pcss` to turn blocker distance into a penumbra width.
+    pub light_size: f32,
+    pub filter: ShadowMapFilter,
+}
+
+impl Default for ShadowSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            size: 1024,
+            bias: 0.0025,
+            slope_bias: 0.005,
+            pcf_samples: 16,
+            pcf_radius: 1.5,
+            light_size: 0.25,
+            filter: ShadowMapFilter::default(),
+        }
+    }
+}
+
+impl ShadowSettings {
+    /// Bias for a surface whose normal makes `cos_theta` with the light.
+    /// Grazing angles get a larger bias to avoid acne. Mirrored in GLSL by
+    /// `slopeScaledBias` in [`SHADOW_SAMPLING_GLSL`].
+    pub fn slope_scaled_bias(&self, cos_theta: f32) -> f32 {
+        let tan_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt() / cos_theta.max(0.001);
+        self.bias + self.slope_bias * tan_theta.min(10.0)
+    }
+}
+
+/// `cos_theta` at which [`ShadowSettings::slope_scaled_bias`] is evaluated to
+/// get `max_slope_bias`, the cap `slopeScaledBias` in [`SHADOW_SAMPLING_GLSL`]
+/// applies (the same `0.05` floor it puts on `NdotL`). `slope_scaled_bias`
+/// itself clamps `tan_theta` at `10.0` and does not read this constant.
+const MIN_COS_THETA: f32 = 0.05;
+
+/// A single shadow map and the faces/views needed to render into it. Point
+/// lights render into all six cube faces with a linear (non-projective)
+/// distance comparison; directional and spot lights render a single
+/// orthographic or perspective depth map respectively.
+pub struct ShadowMap {
+    pub texture: GpuTexture,
+    pub settings: ShadowSettings,
+}
+
+impl ShadowMap {
+    /// Allocates a new depth-only shadow map. `point` selects a cube map
+    /// (used for point lights with linear distance comparison) vs a single
+    /// 2D depth texture (directional/spot lights).
+    pub fn new(settings: ShadowSettings, point: bool) -> Result<Self, String> {
+        let kind = if point {
+            GpuTextureKind::Cube { size: settings.size }
+        } else {
+            GpuTextureKind::Rectangle {
+                width: settings.size,
+                height: settings.size,
+            }
+        };
+
+        let texture = GpuTexture::new(kind, PixelKind::D32F, None)?;
+
+        Ok(Self { texture, settings })
+    }
+
+    /// Computes the uniform values [`SHADOW_SAMPLING_GLSL`] needs to sample
+    /// this shadow map. `world_extent` is the width of the light's frustum
+    /// in world units (`2 * scene_radius` for directional, the cone's base
+    /// diameter for spot, `2 * range` for point).
+    pub fn sampling_uniforms(&self, world_extent: f32) -> ShadowSamplingUniforms {
+        ShadowSamplingUniforms {
+            poisson_disk: POISSON_DISK.map(|tap| (tap.x, tap.y)),
+            bias: self.settings.bias,
+            slope_bias: self.settings.slope_bias,
+            max_slope_bias: self.settings.slope_scaled_bias(MIN_COS_THETA),
+            pcf_radius: self.settings.pcf_radius,
+            sample_count: self.settings.pcf_samples,
+            light_size: self.settings.light_size,
+            texels_per_world_unit: self.settings.size as f32 / world_extent.max(0.001),
+            filter_mode: self.settings.filter as i32,
+        }
+    }
+
+    /// Previews the `Pcss` penumbra width for editor tooling, without a GPU
+    /// round-trip. Mirrors `estimatePenumbra` in [`SHADOW_SAMPLING_GLSL`].
+    pub fn preview_penumbra_width(&self, receiver_depth: f32, avg_blocker_depth: f32) -> f32 {
+        pcss_penumbra_width(receiver_depth, avg_blocker_depth, self.settings.light_size)
+    }
+}
+
+/// Host-computed snapshot of every uniform [`SHADOW_SAMPLING_GLSL`] needs in
+/// order to sample a [`ShadowMap`]. Recomputed whenever a light's
+/// `ShadowSettings` change (see [`ShadowMapRenderer::get_or_recreate`]).
+#[derive(Copy, Clone, Debug)]
+pub struct ShadowSamplingUniforms {
+    pub poisson_disk: [(f32, f32); 16],
+    pub bias: f32,
+    pub slope_bias: f32,
+    pub max_slope_bias: f32,
+    pub pcf_radius: f32,
+    pub sample_count: usize,
+    pub light_size: f32,
+    /// Shadow map texels per world unit at this light's frustum, used to
+    /// turn `estimatePenumbra`'s world-space result into a texel radius.
+    pub texels_per_world_unit: f32,
+    /// `ShadowMapFilter` as the `filterMode` int `sampleShadow` switches on
+    /// (`None` = 0, ..., `Pcss` = 3). `samplePointShadow` takes no filter mode.
+    pub filter_mode: i32,
+}
+
+/// Fixed Poisson disk used to jitter `Pcf`/`Pcss` taps. Rotated per-pixel in
+/// GLSL by `rotatedPoissonTap` to hide banding instead of resampling it.
+pub const POISSON_DISK: [Vec3; 16] = [
+    Vec3 { x: -0.942_016_24, y: -0.399_062_16, z: 0.0 },
+    Vec3 { x: 0.945_586_1, y: -0.768_907_36, z: 0.0 },
+    Vec3 { x: -0.094_184_1, y: -0.929_388_7, z: 0.0 },
+    Vec3 { x: 0.344_959_38, y: 0.293_877_76, z: 0.0 },
+    Vec3 { x: -0.915_886_6, y: 0.457_714_43, z: 0.0 },
+    Vec3 { x: -0.815_442_3, y: -0.879_123_6, z: 0.0 },
+    Vec3 { x: -0.382_775_1, y: 0.276_768_5, z: 0.0 },
+    Vec3 { x: 0.974_843_9, y: 0.756_751_65, z: 0.0 },
+    Vec3 { x: 0.443_233_5, y: -0.975_088_4, z: 0.0 },
+    Vec3 { x: 0.537_429_6, y: 0.473_734_98, z: 0.0 },
+    Vec3 { x: -0.264_969_1, y: -0.418_930_05, z: 0.0 },
+    Vec3 { x: 0.791_975_14, y: 0.190_901_2, z: 0.0 },
+    Vec3 { x: -0.241_888_9, y: 0.997_065_25, z: 0.0 },
+    Vec3 { x: -0.614_452_6, y: -0.057_280_5, z: 0.0 },
+    Vec3 { x: 0.186_985_9, y: -0.578_485_9, z: 0.0 },
+    Vec3 { x: 0.032_947_0, y: 0.810_382_6, z: 0.0 },
+];
+
+/// World-space `Pcss` penumbra width from the average blocker depth found
+/// during the blocker search. Mirrored in GLSL by `estimatePenumbra`.
+pub fn pcss_penumbra_width(receiver_depth: f32, avg_blocker_depth: f32, light_size: f32) -> f32 {
+    if avg_blocker_depth <= 0.0 || avg_blocker_depth >= receiver_depth {
+        return 0.0;
+    }
+    (receiver_depth - avg_blocker_depth) / avg_blocker_depth * light_size
+}
+
+/// Builds the view-projection matrix for a directional light's single
+/// orthographic shadow map. Framed tightly around `focus` (typically the
+/// view frustum's center) out to `scene_radius`, since a directional light
+/// has no meaningful position of its own to build a frustum from.
+pub fn directional_light_matrix(direction: Vec3, focus: Vec3, scene_radius: f32) -> Mat4 {
+    let direction = direction.normalized();
+    let eye = focus - direction.scale(scene_radius);
+    let view = Mat4::look_at(eye, focus, non_degenerate_up(direction));
+    let projection = Mat4::ortho(-scene_radius, scene_radius, -scene_radius, scene_radius, 0.0, scene_radius * 2.0);
+    projection * view
+}
+
+/// An up vector for `Mat4::look_at(.., .., up)` that stays well away from
+/// parallel to `direction` - world up `(0, 1, 0)` degenerates for a
+/// straight-down (or straight-up) directional light, a common case for a
+/// midday sun.
+fn non_degenerate_up(direction: Vec3) -> Vec3 {
+    let world_up = Vec3::new(0.0, 1.0, 0.0);
+    if direction.dot(world_up).abs() > 0.999 {
+        Vec3::new(0.0, 0.0, 1.0)
+    } else {
+        world_up
+    }
+}
+
+/// Builds the view-projection matrix for a spot light's single perspective
+/// shadow map.
+pub fn spot_light_matrix(position: Vec3, direction: Vec3, full_cone_angle: f32, range: f32) -> Mat4 {
+    let view = Mat4::look_at(position, position + direction, Vec3::new(0.0, 1.0, 0.0));
+    let projection = Mat4::perspective(full_cone_angle, 1.0, 0.01, range);
+    projection * view
+}
+
+/// Per-face view-projection matrices for a point light's cube shadow map, in
+/// the conventional +X,-X,+Y,-Y,+Z,-Z cube map face order. Rendered into the
+/// cube texture allocated by `ShadowMap::new(settings, true)` one face at a
+/// time.
+pub fn point_light_cube_matrices(position: Vec3, range: f32) -> [Mat4; 6] {
+    let projection = Mat4::perspective(std::f32::consts::FRAC_PI_2, 1.0, 0.01, range);
+
+    let faces = [
+        (Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, -1.0, 0.0)),
+        (Vec3::new(-1.0, 0.0, 0.0), Vec3::new(0.0, -1.0, 0.0)),
+        (Vec3::new(0.0, 1.0, 0.0), Vec3::new(0.0, 0.0, 1.0)),
+        (Vec3::new(0.0, -1.0, 0.0), Vec3::new(0.0, 0.0, -1.0)),
+        (Vec3::new(0.0, 0.0, 1.0), Vec3::new(0.0, -1.0, 0.0)),
+        (Vec3::new(0.0, 0.0, -1.0), Vec3::new(0.0, -1.0, 0.0)),
+    ];
+
+    let mut matrices = [Mat4::IDENTITY; 6];
+    for (i, (forward, up)) in faces.iter().enumerate() {
+        matrices[i] = projection * Mat4::look_at(position, position + *forward, *up);
+    }
+    matrices
+}
+
+/// Depth-only vertex/fragment pair used to render shadow casters into a
+/// directional or spot light's single depth map. Point lights use
+/// [`POINT_DEPTH_FRAGMENT_SHADER`] instead, since a cube map needs linear
+/// distance rather than post-projective depth to be comparable across faces.
+pub const DEPTH_VERTEX_SHADER: &str = r#"
+#version 330 core
+layout(location = 0) in vec3 vertexPosition;
+uniform mat4 worldViewProjection;
+void main() {
+    gl_Position = worldViewProjection * vec4(vertexPosition, 1.0);
+}
+"#;
+
+pub const DEPTH_FRAGMENT_SHADER: &str = r#"
+#version 330 core
+void main() {
+    // Depth is written automatically to the bound D32F attachment.
+}
+"#;
+
+pub const POINT_DEPTH_VERTEX_SHADER: &str = r#"
+#version 330 core
+layout(location = 0) in vec3 vertexPosition;
+uniform mat4 worldMatrix;
+uniform mat4 worldViewProjection;
+out vec3 fragPosition;
+void main() {
+    fragPosition = (worldMatrix * vec4(vertexPosition, 1.0)).xyz;
+    gl_Position = worldViewProjection * vec4(vertexPosition, 1.0);
+}
+"#;
+
+pub const POINT_DEPTH_FRAGMENT_SHADER: &str = r#"
+#version 330 core
+in vec3 fragPosition;
+uniform vec3 lightPosition;
+uniform float lightRange;
+out float fragDistance;
+void main() {
+    fragDistance = length(fragPosition - lightPosition) / lightRange;
+}
+"#;
+
+/// GLSL source injected into the main lighting pass's fragment shader.
+/// Exposes:
+/// - `sampleShadow(shadowMap, lightSpacePosition, NdotL, settings...)` for
+///   directional/spot lights - projects into light space, slope-scale biases
+///   the comparison, then dispatches to hardware/PCF/PCSS filtering.
+/// - `samplePointShadow(shadowCube, worldPosition, lightPosition, lightRange, NdotL, settings...)`
+///   for point lights - a single comparison tap with no PCF/PCSS filtering,
+///   comparing linear distance instead of projective depth (matching what
+///   [`POINT_DEPTH_FRAGMENT_SHADER`] writes).
+///
+/// Both resolve to `0.0` (fully shadowed) .. `1.0` (fully lit).
+pub const SHADOW_SAMPLING_GLSL: &str = r#"
+uniform vec2 poissonDisk[16];
+
+float slopeScaledBias(float bias, float slopeBias, float maxSlopeBias, float NdotL) {
+    float tanTheta = sqrt(max(1.0 - NdotL * NdotL, 0.0)) / max(NdotL, 0.05);
+    return min(bias + slopeBias * tanTheta, maxSlopeBias);
+}
+
+// Rotates the precomputed Poisson disk per-pixel by an interleaved-gradient
+// noise angle, turning banding between PCF taps into much less visible
+// dithering.
+vec2 rotatedPoissonTap(int i, vec2 screenUv) {
+    float noise = fract(52.9829189 * fract(dot(screenUv, vec2(0.06711056, 0.00583715))));
+    float angle = noise * 6.2831853;
+    float s = sin(angle);
+    float c = cos(angle);
+    vec2 tap = poissonDisk[i];
+    return vec2(tap.x * c - tap.y * s, tap.x * s + tap.y * c);
+}
+
+float pcfFilter(sampler2D shadowMap, vec2 uv, float compareDepth, float radiusTexels, float texelSize, vec2 screenUv, int sampleCount) {
+    float occlusion = 0.0;
+    for (int i = 0; i < sampleCount; ++i) {
+        vec2 offset = rotatedPoissonTap(i, screenUv) * radiusTexels * texelSize;
+        occlusion += texture(shadowMap, uv + offset).r < compareDepth ? 0.0 : 1.0;
+    }
+    return occlusion / float(sampleCount);
+}
+
+// Averages blocker depth over a wide search region; returns -1.0 when no
+// blocker is found (the receiver is fully lit, nothing to soften).
+float pcssBlockerSearch(sampler2D shadowMap, vec2 uv, float compareDepth, float searchRadiusTexels, float texelSize, vec2 screenUv, int sampleCount) {
+    float blockerSum = 0.0;
+    int blockerCount = 0;
+    for (int i = 0; i < sampleCount; ++i) {
+        vec2 offset = rotatedPoissonTap(i, screenUv) * searchRadiusTexels * texelSize;
+        float sampleDepth = texture(shadowMap, uv + offset).r;
+        if (sampleDepth < compareDepth) {
+            blockerSum += sampleDepth;
+            blockerCount += 1;
+        }
+    }
+    return blockerCount > 0 ? blockerSum / float(blockerCount) : -1.0;
+}
+
+// World-space penumbra width - same formula as `pcss_penumbra_width` on the
+// host, kept in lockstep with it. Callers must scale this into texels via
+// `texelsPerWorldUnit` before using it as a filter radius.
+float estimatePenumbra(float receiverDepth, float avgBlockerDepth, float lightSize) {
+    return (receiverDepth - avgBlockerDepth) / avgBlockerDepth * lightSize;
+}
+
+float pcssFilter(sampler2D shadowMap, vec2 uv, float compareDepth, float texelSize, vec2 screenUv, int sampleCount, float lightSize, float texelsPerWorldUnit) {
+    // Seed the blocker search with the light's own footprint in texels
+    // rather than an arbitrary sample-count-sized radius.
+    float searchRadiusTexels = max(lightSize * texelsPerWorldUnit, 1.0);
+    float avgBlocker = pcssBlockerSearch(shadowMap, uv, compareDepth, searchRadiusTexels, texelSize, screenUv, sampleCount);
+    if (avgBlocker < 0.0) {
+        return 1.0;
+    }
+    float penumbra = estimatePenumbra(compareDepth, avgBlocker, lightSize);
+    float radiusTexels = max(penumbra * texelsPerWorldUnit, 1.0);
+    return pcfFilter(shadowMap, uv, compareDepth, radiusTexels, texelSize, screenUv, sampleCount);
+}
+
+// filterMode: 0 = None, 1 = Hardware2x2, 2 = Pcf, 3 = Pcss - see ShadowMapFilter.
+float sampleShadow(sampler2D shadowMap, vec4 lightSpacePosition, float NdotL, vec2 screenUv,
+                    float bias, float slopeBias, float maxSlopeBias, float pcfRadius, int sampleCount,
+                    float lightSize, float texelsPerWorldUnit, int filterMode) {
+    vec3 proj = lightSpacePosition.xyz / lightSpacePosition.w * 0.5 + 0.5;
+    if (proj.z > 1.0) {
+        return 1.0;
+    }
+    float texelSize = 1.0 / float(textureSize(shadowMap, 0).x);
+    float compareDepth = proj.z - slopeScaledBias(bias, slopeBias, maxSlopeBias, NdotL);
+    if (filterMode == 3) {
+        return pcssFilter(shadowMap, proj.xy, compareDepth, texelSize, screenUv, sampleCount, lightSize, texelsPerWorldUnit);
+    } else if (filterMode == 2) {
+        return pcfFilter(shadowMap, proj.xy, compareDepth, pcfRadius, texelSize, screenUv, sampleCount);
+    }
+    // None and Hardware2x2 both resolve to a single comparison tap here;
+    // shadowMap is a plain sampler2D, so no hardware comparison filtering
+    // is applied.
+    return texture(shadowMap, proj.xy).r < compareDepth ? 0.0 : 1.0;
+}
+
+float samplePointShadow(samplerCube shadowCube, vec3 worldPosition, vec3 lightPosition, float lightRange,
+                         float NdotL, vec2 screenUv, float bias, float slopeBias, float maxSlopeBias) {
+    vec3 toFragment = worldPosition - lightPosition;
+    float compareDistance = length(toFragment) / lightRange - slopeScaledBias(bias, slopeBias, maxSlopeBias, NdotL);
+    return texture(shadowCube, toFragment).r < compareDistance ? 0.0 : 1.0;
+}
+"#;
+
+/// Splices [`SHADOW_SAMPLING_GLSL`] into `fragment_shader_source`, right
+/// after its `#version` line, so the lighting pass's fragment shader gets
+/// `sampleShadow`/`samplePointShadow` without hand-concatenating strings at
+/// every call site.
+pub fn inject_shadow_sampling(fragment_shader_source: &str) -> String {
+    match fragment_shader_source.find('\n') {
+        Some(newline) => {
+            let (version_line, rest) = fragment_shader_source.split_at(newline + 1);
+            format!("{}{}{}", version_line, SHADOW_SAMPLING_GLSL, rest)
+        }
+        None => format!("{}{}", fragment_shader_source, SHADOW_SAMPLING_GLSL),
+    }
+}
+
+/// Owns every shadow-casting light's [`ShadowMap`], keyed by the light
+/// node's handle so a light that moves or changes settings reuses its
+/// existing GPU allocation instead of reallocating every frame.
+///
+/// `Renderer::render` calls [`render_directional`](ShadowMapRenderer::render_directional) /
+/// [`render_spot`](ShadowMapRenderer::render_spot) / [`render_point`](ShadowMapRenderer::render_point)
+/// once per shadow-casting light before the main lighting pass, binds the
+/// resulting `ShadowMap::texture`, and uploads the [`ShadowSamplingUniforms`]
+/// handed back through `draw_casters` alongside a fragment shader compiled
+/// via [`inject_shadow_sampling`]. `Renderer` isn't part of this checkout, so
+/// that call site can't live here.
+#[derive(Default)]
+pub struct ShadowMapRenderer {
+    maps: HashMap<Handle<Node>, ShadowMap>,
+}
+
+impl ShadowMapRenderer {
+    pub fn new() -> Self {
+        Self { maps: HashMap::new() }
+    }
+
+    /// The currently-built shadow map for a light, if any. `None` until the
+    /// first `render_*` call for that light, or once shadows are disabled
+    /// for it via `ShadowSettings::enabled`.
+    pub fn shadow_map(&self, light: Handle<Node>) -> Option<&ShadowMap> {
+        self.maps.get(&light)
+    }
+
+    fn get_or_recreate(&mut self, light: Handle<Node>, settings: ShadowSettings, point: bool) -> Result<&mut ShadowMap, String> {
+        let needs_new = match self.maps.get(&light) {
+            Some(map) => matches!(map.texture.kind(), GpuTextureKind::Cube { .. }) != point
+                || map.settings.size != settings.size,
+            None => true,
+        };
+
+        if needs_new {
+            self.maps.insert(light, ShadowMap::new(settings, point)?);
+        } else if let Some(map) = self.maps.get_mut(&light) {
+            map.settings = settings;
+        }
+
+        Ok(self.maps.get_mut(&light).expect("just inserted or already present"))
+    }
+
+    /// Renders a directional light's single orthographic depth map.
+    /// `draw_casters` is called once with the light-space view-projection
+    /// matrix and the uniforms the main lighting pass will need to sample
+    /// the resulting map, and is expected to bind the depth program, set
+    /// `worldViewProjection` per object and draw every shadow-casting mesh's
+    /// depth-only geometry into `ShadowMap::texture`.
+    pub fn render_directional(
+        &mut self,
+        light: Handle<Node>,
+        direction: Vec3,
+        focus: Vec3,
+        scene_radius: f32,
+        settings: ShadowSettings,
+        draw_casters: impl FnOnce(&ShadowMap, &Mat4, &ShadowSamplingUniforms),
+    ) -> Result<(), String> {
+        if !settings.enabled {
+            self.maps.remove(&light);
+            return Ok(());
+        }
+
+        let view_projection = directional_light_matrix(direction, focus, scene_radius);
+        let map = self.get_or_recreate(light, settings, false)?;
+        let uniforms = map.sampling_uniforms(scene_radius * 2.0);
+        draw_casters(map, &view_projection, &uniforms);
+
+        Ok(())
+    }
+
+    /// Renders a spot light's single perspective depth map. Same contract as
+    /// [`ShadowMapRenderer::render_directional`].
+    pub fn render_spot(
+        &mut self,
+        light: Handle<Node>,
+        position: Vec3,
+        direction: Vec3,
+        full_cone_angle: f32,
+        range: f32,
+        settings: ShadowSettings,
+        draw_casters: impl FnOnce(&ShadowMap, &Mat4, &ShadowSamplingUniforms),
+    ) -> Result<(), String> {
+        if !settings.enabled {
+            self.maps.remove(&light);
+            return Ok(());
+        }
+
+        let view_projection = spot_light_matrix(position, direction, full_cone_angle, range);
+        let map = self.get_or_recreate(light, settings, false)?;
+        // Diameter of the light's cone at `range`, its widest extent.
+        let world_extent = 2.0 * range * (full_cone_angle * 0.5).tan();
+        let uniforms = map.sampling_uniforms(world_extent);
+        draw_casters(map, &view_projection, &uniforms);
+
+        Ok(())
+    }
+
+    /// Renders a point light's six cube-face depth maps, comparing linear
+    /// distance (see [`POINT_DEPTH_FRAGMENT_SHADER`]) rather than projective
+    /// depth, since no single projection covers a point light's full sphere
+    /// of influence. `draw_casters` is called once per face.
+    pub fn render_point(
+        &mut self,
+        light: Handle<Node>,
+        position: Vec3,
+        range: f32,
+        settings: ShadowSettings,
+        mut draw_casters: impl FnMut(&ShadowMap, usize, &Mat4, &ShadowSamplingUniforms),
+    ) -> Result<(), String> {
+        if !settings.enabled {
+            self.maps.remove(&light);
+            return Ok(());
+        }
+
+        let view_projections = point_light_cube_matrices(position, range);
+        let map = self.get_or_recreate(light, settings, true)?;
+        let uniforms = map.sampling_uniforms(2.0 * range);
+
+        for (face, view_projection) in view_projections.iter().enumerate() {
+            draw_casters(map, face, view_projection, &uniforms);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pcss_penumbra_width_scales_with_blocker_distance() {
+        assert_eq!(pcss_penumbra_width(2.0, 1.0, 0.5), 0.5);
+    }
+
+    #[test]
+    fn pcss_penumbra_width_is_zero_with_no_blocker() {
+        assert_eq!(pcss_penumbra_width(1.0, 2.0, 0.5), 0.0);
+        assert_eq!(pcss_penumbra_width(1.0, 0.0, 0.5), 0.0);
+    }
+
+    #[test]
+    fn slope_scaled_bias_is_just_the_constant_term_head_on() {
+        let settings = ShadowSettings::default();
+        assert!((settings.slope_scaled_bias(1.0) - settings.bias).abs() < 1e-6);
+    }
+
+    #[test]
+    fn slope_scaled_bias_grows_at_grazing_angles_but_stays_clamped() {
+        let settings = ShadowSettings::default();
+        let grazing = settings.slope_scaled_bias(MIN_COS_THETA);
+        assert!(grazing > settings.bias);
+        assert!((grazing - (settings.bias + settings.slope_bias * 10.0)).abs() < 1e-6);
+    }
+}
@@ -1,6 +1,6 @@
 use std::path::*;
 use crate::{
-    renderer::gpu_texture::GpuTexture,
+    renderer::{gpu_texture::GpuTexture, framebuffer::FrameBuffer},
     core::visitor::{
         Visit,
         VisitResult,
@@ -17,7 +17,27 @@ pub struct Texture {
     pub(in crate) gpu_tex: Option<GpuTexture>,
     pub(in crate) bytes: Vec<u8>,
     pub(in crate) kind: TextureKind,
-    pub(in crate) loaded: bool
+    pub(in crate) loaded: bool,
+    /// Framebuffer this texture is the color attachment of, when the texture
+    /// was created via [`Texture::new_render_target`] instead of being
+    /// decoded from a file. `None` for ordinary file-backed textures, and
+    /// also `None` right after deserialization - the framebuffer is GPU
+    /// state and is rebuilt by whatever scene node owns this texture. Use
+    /// [`Texture::is_render_target`] (backed by the persisted
+    /// `is_render_target` flag below) to tell the two apart instead.
+    pub(in crate) render_target: Option<FrameBuffer>,
+    /// Persisted discriminator mirroring `render_target.is_some()`. Needed
+    /// because `render_target` itself is never serialized (it's GPU state),
+    /// so without this flag a deserialized render-target texture would look
+    /// just like an ordinary, reloadable file-backed one.
+    pub(in crate) is_render_target: bool,
+    /// Whether a full mip chain should be built for this texture. Only takes
+    /// effect for uncompressed `kind`s - block-compressed textures are
+    /// expected to already carry their mip chain in the source file.
+    pub(in crate) generate_mips: bool,
+    /// Box-downsampled mip levels past level 0, smallest last. Empty unless
+    /// `generate_mips` is set and `kind` supports software mip generation.
+    pub(in crate) mip_chain: Vec<Vec<u8>>,
 }
 
 impl Default for Texture {
@@ -29,7 +49,11 @@ impl Default for Texture {
             gpu_tex: None,
             bytes: Vec::new(),
             kind: TextureKind::RGBA8,
-            loaded: false
+            loaded: false,
+            render_target: None,
+            is_render_target: false,
+            generate_mips: false,
+            mip_chain: Vec::new(),
         }
     }
 }
@@ -44,8 +68,30 @@ impl Visit for Texture {
             self.kind = TextureKind::new(kind)?;
         }
 
+        // Scenes saved before render targets existed have no
+        // "IsRenderTarget" region - default to `false` rather than failing
+        // the whole load over a missing optional field.
+        if self.is_render_target.visit("IsRenderTarget", visitor).is_err() {
+            self.is_render_target = false;
+        }
+
+        // Render target textures have no meaningful on-disk representation
+        // beyond their kind: their pixels live entirely on the GPU and the
+        // framebuffer is re-created by whatever scene node owns this
+        // texture, so there is nothing else useful to persist.
+        if self.is_render_target {
+            return visitor.leave_region();
+        }
+
         self.path.visit("Path", visitor)?;
 
+        // Scenes saved before mip generation existed have no "GenerateMips"
+        // region - don't fail the whole load over a missing optional field,
+        // just fall back to the pre-existing behavior of not generating mips.
+        if self.generate_mips.visit("GenerateMips", visitor).is_err() {
+            self.generate_mips = false;
+        }
+
         visitor.leave_region()
     }
 }
@@ -55,14 +101,32 @@ pub enum TextureKind {
     R8,
     RGB8,
     RGBA8,
+    /// `RGB8` decoded in sRGB color space - the renderer linearizes it on
+    /// sample instead of treating its bytes as already-linear.
+    SRGB8,
+    /// `RGBA8` decoded in sRGB color space, alpha channel stays linear.
+    SRGBA8,
+    /// GPU block-compressed RGB, no alpha (a.k.a. DXT1). Trades quality for
+    /// a quarter of the VRAM of `RGB8`.
+    DXT1,
+    /// GPU block-compressed RGBA with interpolated alpha (a.k.a. DXT5).
+    /// Quarter of the VRAM of `RGBA8`.
+    DXT5,
 }
 
 impl TextureKind {
+    /// Numbering here is append-only and must never change for existing
+    /// variants - it is what's stored in serialized scenes, so renumbering
+    /// would silently corrupt every texture reference on load.
     pub fn new(id: u32) -> Result<Self, String> {
         match id {
             0 => Ok(TextureKind::R8),
             1 => Ok(TextureKind::RGB8),
             2 => Ok(TextureKind::RGBA8),
+            3 => Ok(TextureKind::SRGB8),
+            4 => Ok(TextureKind::SRGBA8),
+            5 => Ok(TextureKind::DXT1),
+            6 => Ok(TextureKind::DXT5),
             _ => Err(format!("Invalid texture kind {}!", id))
         }
     }
@@ -72,37 +136,205 @@ impl TextureKind {
             TextureKind::R8 => 0,
             TextureKind::RGB8 => 1,
             TextureKind::RGBA8 => 2,
+            TextureKind::SRGB8 => 3,
+            TextureKind::SRGBA8 => 4,
+            TextureKind::DXT1 => 5,
+            TextureKind::DXT5 => 6,
         }
     }
+
+    /// Bytes per texel for software mip generation. Block-compressed kinds
+    /// have no meaningful per-texel size and don't support it - see
+    /// [`TextureKind::supports_software_mips`].
+    fn bytes_per_pixel(self) -> usize {
+        match self {
+            TextureKind::R8 => 1,
+            TextureKind::RGB8 | TextureKind::SRGB8 => 3,
+            TextureKind::RGBA8 | TextureKind::SRGBA8 => 4,
+            TextureKind::DXT1 | TextureKind::DXT5 => 0,
+        }
+    }
+
+    /// Whether box-downsample mip generation on the CPU applies to this
+    /// kind. Compressed textures are expected to arrive with their mip chain
+    /// already baked into the source file.
+    fn supports_software_mips(self) -> bool {
+        self.bytes_per_pixel() > 0
+    }
 }
 
 impl Texture {
-    pub(in crate) fn load_from_file<P: AsRef<Path>>(path: P, kind: TextureKind) -> Result<Texture, image::ImageError> {
+    pub(in crate) fn load_from_file<P: AsRef<Path>>(path: P, kind: TextureKind, generate_mips: bool) -> Result<Texture, image::ImageError> {
         let dyn_img = image::open(path.as_ref())?;
 
+        let mut texture = Self::from_dynamic_image(dyn_img, kind, generate_mips);
+        texture.path = path.as_ref().to_path_buf();
+        Ok(texture)
+    }
+
+    /// Decodes a texture from an in-memory byte buffer rather than a file on
+    /// disk, using the same decoder as [`Texture::load_from_file`]. This is
+    /// what lets the resource manager register textures embedded inline in a
+    /// model (a glTF `.bin` buffer view, a base64 data URI, etc.) under a
+    /// synthetic path key instead of a real file.
+    pub(in crate) fn load_from_memory(bytes: &[u8], kind: TextureKind, generate_mips: bool) -> Result<Texture, image::ImageError> {
+        let dyn_img = image::load_from_memory(bytes)?;
+
+        Ok(Self::from_dynamic_image(dyn_img, kind, generate_mips))
+    }
+
+    fn from_dynamic_image(dyn_img: image::DynamicImage, kind: TextureKind, generate_mips: bool) -> Texture {
         let width = dyn_img.width();
         let height = dyn_img.height();
 
         let bytes = match kind {
             TextureKind::R8 => dyn_img.to_luma().into_raw(),
-            TextureKind::RGB8 => dyn_img.to_rgb().into_raw(),
-            TextureKind::RGBA8 => dyn_img.to_rgba().into_raw(),
+            TextureKind::RGB8 | TextureKind::SRGB8 => dyn_img.to_rgb().into_raw(),
+            TextureKind::RGBA8 | TextureKind::SRGBA8 => dyn_img.to_rgba().into_raw(),
+            // Block-compressed kinds aren't produced by decoding a regular
+            // image file - callers load those straight out of a DDS/KTX
+            // container instead. Fall back to an uncompressed RGBA decode so
+            // the texture is at least usable rather than empty.
+            TextureKind::DXT1 | TextureKind::DXT5 => dyn_img.to_rgba().into_raw(),
+        };
+
+        let generate_mips = generate_mips && kind.supports_software_mips();
+        let mip_chain = if generate_mips {
+            build_mip_chain(&bytes, width, height, kind.bytes_per_pixel())
+        } else {
+            Vec::new()
         };
 
-        Ok(Texture {
+        Texture {
             kind,
             width,
             height,
             bytes,
-            path: path.as_ref().to_path_buf(),
+            path: PathBuf::new(),
             gpu_tex: None,
             loaded: true,
-        })
+            render_target: None,
+            is_render_target: false,
+            generate_mips,
+            mip_chain,
+        }
+    }
+
+    /// Total number of mip levels, including level 0. Always `1` when
+    /// `generate_mips` wasn't requested or the texture's `kind` doesn't
+    /// support software mip generation.
+    pub fn mip_count(&self) -> usize {
+        1 + self.mip_chain.len()
+    }
+
+    /// Creates a new texture that renders-to rather than is-loaded-from. The
+    /// returned `Texture` owns a framebuffer with a color attachment of the
+    /// requested `kind` (e.g. `RGBA8`) plus a depth attachment, and can be
+    /// passed to `Renderer::render_scene_to_texture` like any other scene
+    /// render target - mirrors, in-world security monitors and pre-rendered
+    /// UI thumbnails are all just a camera rendering into one of these.
+    ///
+    /// Render target textures participate in the normal material/resource
+    /// pipeline, but are never reloaded from `path` on deserialization - see
+    /// [`Texture::is_render_target`].
+    pub fn new_render_target(width: u32, height: u32, kind: TextureKind) -> Texture {
+        Texture {
+            kind,
+            width,
+            height,
+            bytes: Vec::new(),
+            // Empty on purpose - there's no source file. ResourceManager
+            // keys render targets by index instead, since this is shared
+            // by every instance otherwise.
+            path: PathBuf::new(),
+            gpu_tex: None,
+            loaded: true,
+            render_target: Some(FrameBuffer::new(width, height, kind)),
+            is_render_target: true,
+            generate_mips: false,
+            mip_chain: Vec::new(),
+        }
+    }
+
+    /// Returns `true` if this texture is a render target created via
+    /// [`Texture::new_render_target`] rather than decoded from a file. Such
+    /// textures are not reloadable - see [`ResourceManager::reload_resources`].
+    pub fn is_render_target(&self) -> bool {
+        self.is_render_target
     }
 
     pub fn is_loaded(&self) -> bool {
         self.loaded
     }
+
+    /// Re-decodes this texture from its source file on disk in place,
+    /// swapping in the new `bytes`/dimensions and dropping the stale
+    /// `GpuTexture` so the renderer re-uploads it on the next
+    /// `upload_resources` call. Used by the resource manager's filesystem
+    /// watcher to hot-reload a texture that changed on disk without
+    /// invalidating any `Arc<Mutex<Texture>>` handles already held by scene
+    /// nodes. No-op for render targets, which have no source file to re-read.
+    pub(in crate) fn reload(&mut self) -> Result<(), image::ImageError> {
+        if self.is_render_target {
+            return Ok(());
+        }
+
+        let reloaded = Self::load_from_file(&self.path, self.kind, self.generate_mips)?;
+
+        self.width = reloaded.width;
+        self.height = reloaded.height;
+        self.bytes = reloaded.bytes;
+        self.mip_chain = reloaded.mip_chain;
+        self.gpu_tex = None;
+        self.loaded = true;
+
+        Ok(())
+    }
+}
+
+/// Builds a full mip chain below level 0 by repeated 2x2 box downsampling,
+/// halving width and height each level (rounding down, minimum of 1) until a
+/// 1x1 level is reached.
+fn build_mip_chain(level0: &[u8], width: u32, height: u32, bytes_per_pixel: usize) -> Vec<Vec<u8>> {
+    let mut chain = Vec::new();
+    let mut prev = level0.to_vec();
+    let (mut w, mut h) = (width, height);
+
+    while w > 1 || h > 1 {
+        let next_w = (w / 2).max(1);
+        let next_h = (h / 2).max(1);
+        let mut next = vec![0u8; (next_w * next_h) as usize * bytes_per_pixel];
+
+        for y in 0..next_h {
+            for x in 0..next_w {
+                let src_x = (x * 2).min(w - 1);
+                let src_y = (y * 2).min(h - 1);
+                let src_x1 = (src_x + 1).min(w - 1);
+                let src_y1 = (src_y + 1).min(h - 1);
+
+                let taps = [
+                    (src_x, src_y), (src_x1, src_y),
+                    (src_x, src_y1), (src_x1, src_y1),
+                ];
+
+                let dst_offset = ((y * next_w + x) as usize) * bytes_per_pixel;
+                for channel in 0..bytes_per_pixel {
+                    let sum: u32 = taps
+                        .iter()
+                        .map(|(tx, ty)| prev[((ty * w + tx) as usize) * bytes_per_pixel + channel] as u32)
+                        .sum();
+                    next[dst_offset + channel] = (sum / taps.len() as u32) as u8;
+                }
+            }
+        }
+
+        chain.push(next.clone());
+        prev = next;
+        w = next_w;
+        h = next_h;
+    }
+
+    chain
 }
 
 impl Drop for Texture {
@@ -111,4 +343,23 @@ impl Drop for Texture {
             Log::writeln(format!("Texture resource {:?} destroyed!", self.path));
         }
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_mip_chain_averages_down_to_a_single_texel() {
+        // 2x2, single channel: 10 20 / 30 40 - averages to one 25 texel.
+        let chain = build_mip_chain(&[10, 20, 30, 40], 2, 2, 1);
+        assert_eq!(chain, vec![vec![25]]);
+    }
+
+    #[test]
+    fn build_mip_chain_keeps_halving_until_1x1() {
+        // 4x1, single channel - halves to 2x1, then to 1x1.
+        let chain = build_mip_chain(&[0, 10, 20, 30], 4, 1, 1);
+        assert_eq!(chain, vec![vec![5, 25], vec![15]]);
+    }
 }
\ No newline at end of file
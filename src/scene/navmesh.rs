@@ -0,0 +1,400 @@
+use std::collections::{BinaryHeap, HashMap};
+use std::cmp::Ordering;
+use crate::core::math::vec3::Vec3;
+
+/// A single walkable triangle, referencing vertices by index into the
+/// navmesh's shared vertex buffer - mirrors how the rest of the scene graph
+/// stores mesh geometry.
+#[derive(Copy, Clone, Debug)]
+pub struct NavmeshTriangle {
+    pub a: u32,
+    pub b: u32,
+    pub c: u32,
+}
+
+impl NavmeshTriangle {
+    fn indices(&self) -> [u32; 3] {
+        [self.a, self.b, self.c]
+    }
+
+    fn edges(&self) -> [(u32, u32); 3] {
+        [(self.a, self.b), (self.b, self.c), (self.c, self.a)]
+    }
+}
+
+/// Arbitrary walkable-surface pathfinding mesh, built from a vertex list and
+/// a triangle index list. Neighbouring triangles are discovered by shared
+/// edges (two triangles that reference the same pair of vertex indices),
+/// and paths are found by running A* over that adjacency graph followed by
+/// a funnel pass that collapses the resulting corridor into a minimal
+/// straight-line path.
+pub struct Navmesh {
+    vertices: Vec<Vec3>,
+    triangles: Vec<NavmeshTriangle>,
+    /// `neighbours[i]` is the list of triangle indices adjacent to triangle `i`.
+    neighbours: Vec<Vec<usize>>,
+}
+
+/// Undirected key for an edge, used to detect when two triangles share it.
+fn edge_key(a: u32, b: u32) -> (u32, u32) {
+    if a < b { (a, b) } else { (b, a) }
+}
+
+impl Navmesh {
+    /// Builds a navmesh from raw geometry, discovering triangle adjacency by
+    /// shared edges.
+    pub fn new(vertices: Vec<Vec3>, triangles: Vec<NavmeshTriangle>) -> Self {
+        let mut edge_to_triangles: HashMap<(u32, u32), Vec<usize>> = HashMap::new();
+        for (i, triangle) in triangles.iter().enumerate() {
+            for (a, b) in triangle.edges().iter() {
+                edge_to_triangles.entry(edge_key(*a, *b)).or_default().push(i);
+            }
+        }
+
+        let mut neighbours = vec![Vec::new(); triangles.len()];
+        for sharing in edge_to_triangles.values() {
+            if sharing.len() == 2 {
+                let (i, j) = (sharing[0], sharing[1]);
+                neighbours[i].push(j);
+                neighbours[j].push(i);
+            }
+        }
+
+        Self {
+            vertices,
+            triangles,
+            neighbours,
+        }
+    }
+
+    fn triangle_vertices(&self, triangle: &NavmeshTriangle) -> (Vec3, Vec3, Vec3) {
+        (
+            self.vertices[triangle.a as usize],
+            self.vertices[triangle.b as usize],
+            self.vertices[triangle.c as usize],
+        )
+    }
+
+    /// Returns `true` if `point` projects inside `triangle`, using the sign
+    /// of the cross product of each edge against the point on the triangle's
+    /// own plane.
+    fn point_in_triangle(&self, point: Vec3, triangle: &NavmeshTriangle) -> bool {
+        let (a, b, c) = self.triangle_vertices(triangle);
+        let normal = (b - a).cross(c - a);
+
+        let sign = |p0: Vec3, p1: Vec3, p2: Vec3| -> f32 {
+            (p1 - p0).cross(p2 - p0).dot(normal)
+        };
+
+        let d1 = sign(a, b, point);
+        let d2 = sign(b, c, point);
+        let d3 = sign(c, a, point);
+
+        let has_neg = d1 < 0.0 || d2 < 0.0 || d3 < 0.0;
+        let has_pos = d1 > 0.0 || d2 > 0.0 || d3 > 0.0;
+
+        !(has_neg && has_pos)
+    }
+
+    /// Finds the first triangle that `point` projects inside, or, for an
+    /// off-mesh query point, the triangle whose centroid is closest to it.
+    /// Only the triangle is chosen; `point` itself is not moved onto the mesh.
+    fn closest_triangle(&self, point: Vec3) -> Option<usize> {
+        if let Some(index) = self.triangles.iter().position(|t| self.point_in_triangle(point, t)) {
+            return Some(index);
+        }
+
+        self.triangles
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| {
+                let da = self.triangle_centroid(a).sub(point).sqr_len();
+                let db = self.triangle_centroid(b).sub(point).sqr_len();
+                da.partial_cmp(&db).unwrap_or(Ordering::Equal)
+            })
+            .map(|(i, _)| i)
+    }
+
+    fn triangle_centroid(&self, triangle: &NavmeshTriangle) -> Vec3 {
+        let (a, b, c) = self.triangle_vertices(triangle);
+        (a + b + c).scale(1.0 / 3.0)
+    }
+
+    /// The shared edge (the "portal") between two adjacent triangles,
+    /// returned as `(left, right)` oriented consistently for walking from
+    /// `from` towards `to` - the funnel algorithm assumes every portal it is
+    /// given follows this same left/right convention, not just whatever
+    /// order the two triangles happen to reference their shared vertices in.
+    fn shared_edge(&self, from: usize, to: usize) -> Option<(Vec3, Vec3)> {
+        let from_indices = self.triangles[from].indices();
+        let to_indices = self.triangles[to].indices();
+
+        let shared: Vec<u32> = from_indices
+            .iter()
+            .copied()
+            .filter(|i| to_indices.contains(i))
+            .collect();
+
+        if shared.len() != 2 {
+            return None;
+        }
+
+        let p0 = self.vertices[shared[0] as usize];
+        let p1 = self.vertices[shared[1] as usize];
+
+        // Orient by winding relative to the direction of travel: `p0` is the
+        // left vertex if it lies on the left of the from-centroid-to-to-centroid
+        // direction (positive 2D cross product on the XZ plane), otherwise swap.
+        let direction = self.triangle_centroid(&self.triangles[to]).sub(self.triangle_centroid(&self.triangles[from]));
+        let to_p0 = p0.sub(self.triangle_centroid(&self.triangles[from]));
+        let cross = direction.x * to_p0.z - direction.z * to_p0.x;
+
+        if cross >= 0.0 {
+            Some((p0, p1))
+        } else {
+            Some((p1, p0))
+        }
+    }
+
+    /// Midpoint of the shared "portal" edge between two adjacent triangles,
+    /// used as the A* graph node for the step cost between them.
+    fn portal_midpoint(&self, from: usize, to: usize) -> Option<Vec3> {
+        self.shared_edge(from, to).map(|(left, right)| (left + right).scale(0.5))
+    }
+
+    /// Finds a path from `from` to `to` across the walkable surface. Returns
+    /// a minimal straight-line path, already string-pulled through the
+    /// corridor of portal edges - not the raw triangle-to-triangle path.
+    pub fn build_path(&self, from: Vec3, to: Vec3) -> Vec<Vec3> {
+        let (Some(start), Some(end)) = (self.closest_triangle(from), self.closest_triangle(to)) else {
+            return Vec::new();
+        };
+
+        if start == end {
+            return vec![from, to];
+        }
+
+        match self.find_corridor(start, end, from) {
+            Some(corridor) => self.funnel(from, to, &corridor),
+            None => Vec::new(),
+        }
+    }
+
+    /// Runs A* over the triangle adjacency graph, using triangle centroid
+    /// distance as the heuristic and shared-edge-midpoint-to-shared-edge-midpoint
+    /// distance as the step cost. Returns the sequence of triangle indices
+    /// from `start` to `end`, inclusive.
+    fn find_corridor(&self, start: usize, end: usize, from: Vec3) -> Option<Vec<usize>> {
+        #[derive(PartialEq)]
+        struct Entry {
+            cost: f32,
+            index: usize,
+        }
+
+        impl Eq for Entry {}
+        impl Ord for Entry {
+            fn cmp(&self, other: &Self) -> Ordering {
+                other.cost.partial_cmp(&self.cost).unwrap_or(Ordering::Equal)
+            }
+        }
+        impl PartialOrd for Entry {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        let heuristic = |i: usize| self.triangle_centroid(&self.triangles[i]).sub(self.triangle_centroid(&self.triangles[end])).len();
+
+        let mut open = BinaryHeap::new();
+        open.push(Entry { cost: heuristic(start), index: start });
+
+        let mut came_from = HashMap::new();
+        let mut g_score = HashMap::new();
+        g_score.insert(start, 0.0f32);
+
+        // Position a triangle was entered from, used to compute the
+        // portal-midpoint step cost into its neighbours. The start triangle
+        // is "entered" at the actual query point rather than a portal.
+        let mut entry_point = HashMap::new();
+        entry_point.insert(start, from);
+
+        while let Some(Entry { index: current, .. }) = open.pop() {
+            if current == end {
+                let mut path = vec![current];
+                let mut node = current;
+                while let Some(prev) = came_from.get(&node) {
+                    path.push(*prev);
+                    node = *prev;
+                }
+                path.reverse();
+                return Some(path);
+            }
+
+            for &neighbour in &self.neighbours[current] {
+                let Some(portal_mid) = self.portal_midpoint(current, neighbour) else {
+                    continue;
+                };
+
+                let step = entry_point[&current].sub(portal_mid).len();
+                let tentative_g = g_score[&current] + step;
+
+                if tentative_g < *g_score.get(&neighbour).unwrap_or(&f32::INFINITY) {
+                    came_from.insert(neighbour, current);
+                    g_score.insert(neighbour, tentative_g);
+                    entry_point.insert(neighbour, portal_mid);
+                    open.push(Entry { cost: tentative_g + heuristic(neighbour), index: neighbour });
+                }
+            }
+        }
+
+        None
+    }
+
+    /// "Simple stupid funnel" string-pulling: walks the corridor of shared
+    /// portal edges, tightening a left/right funnel and emitting a new
+    /// straight segment every time the funnel collapses, which turns a
+    /// vertex-to-vertex path into a minimal path across large polygons.
+    fn funnel(&self, from: Vec3, to: Vec3, corridor: &[usize]) -> Vec<Vec3> {
+        let mut portals = vec![(from, from)];
+        for window in corridor.windows(2) {
+            if let Some(portal) = self.shared_edge(window[0], window[1]) {
+                portals.push(portal);
+            }
+        }
+        portals.push((to, to));
+
+        let mut path = vec![from];
+        let mut apex = from;
+        let mut left = portals[1].0;
+        let mut right = portals[1].1;
+        let mut apex_index = 0usize;
+        let mut left_index = 1usize;
+        let mut right_index = 1usize;
+
+        // Signed area of the triangle projected onto the XZ plane - navmeshes
+        // are assumed mostly horizontal, same convention Recast/Detour use
+        // for their funnel implementation.
+        let triarea2 = |a: Vec3, b: Vec3, c: Vec3| -> f32 {
+            (c.x - a.x) * (b.z - a.z) - (b.x - a.x) * (c.z - a.z)
+        };
+
+        let mut i = 2usize;
+        while i < portals.len() {
+            let (portal_left, portal_right) = portals[i];
+            let mut restarted = false;
+
+            if triarea2(apex, right, portal_right) <= 0.0 {
+                if apex == right || triarea2(apex, left, portal_right) > 0.0 {
+                    right = portal_right;
+                    right_index = i;
+                } else {
+                    path.push(left);
+                    apex = left;
+                    apex_index = left_index;
+                    left = apex;
+                    right = apex;
+                    left_index = apex_index;
+                    right_index = apex_index;
+                    i = apex_index;
+                    restarted = true;
+                }
+            }
+
+            // Skip the left-side test this iteration when the right-side
+            // update just moved the apex and restarted the scan - the
+            // funnel is degenerate (apex == left == right) until the next
+            // portal is examined from the new apex.
+            if !restarted && triarea2(apex, left, portal_left) >= 0.0 {
+                if apex == left || triarea2(apex, right, portal_left) < 0.0 {
+                    left = portal_left;
+                    left_index = i;
+                } else {
+                    path.push(right);
+                    apex = right;
+                    apex_index = right_index;
+                    left = apex;
+                    right = apex;
+                    left_index = apex_index;
+                    right_index = apex_index;
+                    i = apex_index;
+                }
+            }
+
+            i += 1;
+        }
+
+        // The final `(to, to)` portal can already have pushed `to` as an apex.
+        if path.last() != Some(&to) {
+            path.push(to);
+        }
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A single square split into two triangles along one diagonal.
+    fn square_navmesh() -> Navmesh {
+        let vertices = vec![
+            Vec3::new(0.0, 0.0, 0.0),
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(1.0, 0.0, 1.0),
+            Vec3::new(0.0, 0.0, 1.0),
+        ];
+        let triangles = vec![
+            NavmeshTriangle { a: 0, b: 1, c: 2 },
+            NavmeshTriangle { a: 0, b: 2, c: 3 },
+        ];
+        Navmesh::new(vertices, triangles)
+    }
+
+    #[test]
+    fn build_path_across_a_convex_square_is_a_straight_line() {
+        let navmesh = square_navmesh();
+        let from = Vec3::new(0.9, 0.0, 0.1);
+        let to = Vec3::new(0.1, 0.0, 0.9);
+
+        assert_eq!(navmesh.build_path(from, to), vec![from, to]);
+    }
+
+    /// Three unit squares arranged as an L - (0,0)-(1,1), (1,0)-(2,1) and
+    /// (0,1)-(1,2) - with the (1,1)-(2,2) square missing, so a path between
+    /// the two far ends has to bend around the inner corner at (1, 0, 1).
+    fn l_shaped_navmesh() -> Navmesh {
+        let vertices = vec![
+            Vec3::new(0.0, 0.0, 0.0), // 0
+            Vec3::new(1.0, 0.0, 0.0), // 1
+            Vec3::new(2.0, 0.0, 0.0), // 2
+            Vec3::new(0.0, 0.0, 1.0), // 3
+            Vec3::new(1.0, 0.0, 1.0), // 4
+            Vec3::new(2.0, 0.0, 1.0), // 5
+            Vec3::new(0.0, 0.0, 2.0), // 6
+            Vec3::new(1.0, 0.0, 2.0), // 7
+        ];
+        let triangles = vec![
+            NavmeshTriangle { a: 0, b: 1, c: 4 },
+            NavmeshTriangle { a: 0, b: 4, c: 3 },
+            NavmeshTriangle { a: 1, b: 2, c: 5 },
+            NavmeshTriangle { a: 1, b: 5, c: 4 },
+            NavmeshTriangle { a: 3, b: 4, c: 7 },
+            NavmeshTriangle { a: 3, b: 7, c: 6 },
+        ];
+        Navmesh::new(vertices, triangles)
+    }
+
+    #[test]
+    fn build_path_around_an_l_shaped_corridor_bends_at_the_inner_corner() {
+        let navmesh = l_shaped_navmesh();
+        let from = Vec3::new(1.9, 0.0, 0.5);
+        let to = Vec3::new(0.5, 0.0, 1.9);
+
+        let path = navmesh.build_path(from, to);
+
+        assert!(path.len() > 2, "expected a bend around the missing corner square, got {:?}", path);
+        assert_eq!(*path.first().unwrap(), from);
+        assert_eq!(*path.last().unwrap(), to);
+        assert_eq!(path, vec![from, Vec3::new(1.0, 0.0, 1.0), to]);
+    }
+}